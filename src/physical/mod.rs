@@ -1,6 +1,10 @@
 //! Physical layer (or PHY), the first part of the networking stack.
 //! This the most low-level and responsible of direct interaction with hardware.
 
+pub mod fault_injector;
+pub mod loopback;
+pub mod pcap_writer;
+
 use crate::time::Instant;
 
 /// Metadata of a packet.
@@ -11,6 +15,8 @@ pub struct PacketMetadata {
 }
 
 /// Describe a given device's capabilities.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DeviceCapabilities {
     /// The device's network medium type.
     ///
@@ -34,8 +40,19 @@ pub struct DeviceCapabilities {
     pub checksum: ChecksumCapabilities,
 }
 
+impl Default for DeviceCapabilities {
+    fn default() -> Self {
+        DeviceCapabilities {
+            medium: Medium::Ethernet,
+            max_transmission_unit: 1536,
+            max_burst_size: None,
+            checksum: ChecksumCapabilities::default(),
+        }
+    }
+}
+
 /// Describe the checksum behaviour for each protocol.
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ChecksumCapabilities {
     pub ipv4: Checksum,
@@ -76,18 +93,51 @@ pub trait Device {
         Self: 't;
 
     /// Creates a receiving token and a transmit token.
+    ///
+    /// The receive token yields the contents of the received frame via
+    /// [`RxToken::consume`], and the accompanying transmit token lets a caller
+    /// reply in place, e.g. for protocols that must answer from the same buffer
+    /// slot (ARP, NDISC). Returns `None` if no frame is available.
     fn receive(&mut self, timestamp: Instant) -> Option<(Self::RxToken<'_>, Self::TxToken<'_>)>;
 
     /// Creates a transmit token.
+    ///
+    /// The token's [`TxToken::consume`] hands the caller a buffer to fill with
+    /// the frame to send. Returns `None` if the device cannot currently accept
+    /// a frame for transmission.
     fn transmit(&mut self, timestamp: Instant) -> Option<Self::TxToken<'_>>;
 
     /// Describe the device capabilities.
-    fn capabilities(&self) -> Option<()>;
+    ///
+    /// ```rust,ignore
+    /// let caps = device.capabilities();
+    /// let ip_mtu = caps.max_transmission_unit - 14; // strip the Ethernet header
+    /// let max_burst_bytes = caps.max_burst_size.map(|burst| burst * caps.max_transmission_unit);
+    /// ```
+    fn capabilities(&self) -> DeviceCapabilities;
 }
 
-pub trait RxToken {}
+/// A token that owns a buffer holding a received frame.
+///
+/// Implementors destroy the token by value inside [`consume`](RxToken::consume),
+/// so the borrow of the device that produced it ends as soon as the closure
+/// returns instead of being extended until a `Drop` impl runs.
+pub trait RxToken {
+    /// Calls `f` with the buffer backing the received frame, destroying the
+    /// token in the process.
+    fn consume<R, F: FnOnce(&mut [u8]) -> R>(self, timestamp: Instant, f: F) -> R;
+}
 
-pub trait TxToken {}
+/// A token that grants access to a device's transmit buffer.
+///
+/// Implementors destroy the token by value inside [`consume`](TxToken::consume),
+/// so the borrow of the device that produced it ends as soon as the closure
+/// returns instead of being extended until a `Drop` impl runs.
+pub trait TxToken {
+    /// Calls `f` with a `len`-octet buffer to fill with the frame to send,
+    /// destroying the token in the process.
+    fn consume<R, F: FnOnce(&mut [u8]) -> R>(self, timestamp: Instant, len: usize, f: F) -> R;
+}
 
 impl Checksum {
     /// Returns whether the checksum should be computed when sending.
@@ -14,6 +14,17 @@ pub enum PcapMode {
     Rx,
 }
 
+/// The timestamp resolution a [`PcapSink`] records its packets with.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PcapResolution {
+    /// Timestamps are recorded with microsecond precision.
+    #[default]
+    Micro,
+    /// Timestamps are recorded with nanosecond precision.
+    Nano,
+}
+
 enum_with_unknown! {
     /// From packet header type.
     pub enum PcapLink(u32) {
@@ -31,11 +42,26 @@ pub trait PcapSink {
     /// This magic number describe the file format and byte order.
     /// The standard is this, which means big-endian.
     const STD_MAGIC_NUMBER: u32 = 0xA1B2C3D4;
+    /// Magic number signalling the nanosecond-resolution variant of the format.
+    const NSEC_MAGIC_NUMBER: u32 = 0xA1B23C4D;
     const MAX_LEN: u32 = u16::MAX as u32;
 
     /// Writes the given content into the sink.
     fn write(&mut self, content: &[u8]);
 
+    /// The timestamp resolution packets are recorded with. Defaults to
+    /// microsecond resolution.
+    fn resolution(&self) -> PcapResolution {
+        PcapResolution::Micro
+    }
+
+    /// The maximum number of bytes captured per packet; longer packets are
+    /// truncated in the sink, though their true on-wire length is still
+    /// recorded. Defaults to [`MAX_LEN`](Self::MAX_LEN).
+    fn snaplen(&self) -> u32 {
+        Self::MAX_LEN
+    }
+
     /// Writes a `u16` in native endianness.
     fn write_u16(&mut self, content: u16) {
         let mut bytes = [0u8, Self::U16_SIZE];
@@ -54,12 +80,16 @@ pub trait PcapSink {
 
     /// Writes a global header into the sink.
     fn global_header(&mut self, link: PcapLink) {
-        self.write_u32(Self::STD_MAGIC_NUMBER);
-        self.write_u16(2); // major version 
+        let magic = match self.resolution() {
+            PcapResolution::Micro => Self::STD_MAGIC_NUMBER,
+            PcapResolution::Nano => Self::NSEC_MAGIC_NUMBER,
+        };
+        self.write_u32(magic);
+        self.write_u16(2); // major version
         self.write_u16(4); // minor version
         self.write_u32(0); // timezone offset compared to utc
         self.write_u32(0); // timestamp accuracy
-        self.write_u32(Self::MAX_LEN); // maximum packet length captured 
+        self.write_u32(self.snaplen()); // maximum packet length captured
         self.write_u32(link.into()); // network link-layer identifier
     }
 
@@ -67,9 +97,15 @@ pub trait PcapSink {
     fn packet_header(&mut self, timestamp: Instant, len: usize) {
         assert!(len <= Self::MAX_LEN as _);
 
+        let fraction = match self.resolution() {
+            PcapResolution::Micro => timestamp.micros() as u32,
+            PcapResolution::Nano => timestamp.micros() as u32 * 1000,
+        };
+        let captured_len = core::cmp::min(len as u32, self.snaplen());
+
         self.write_u32(timestamp.secs() as _); // timestamp interval
-        self.write_u32(timestamp.micros() as _);
-        self.write_u32(len as _); // bytes actually captured
+        self.write_u32(fraction);
+        self.write_u32(captured_len); // bytes actually captured
         self.write_u32(len as _); // actual packet length on the wire
     }
 
@@ -77,7 +113,8 @@ pub trait PcapSink {
     /// into the sink.
     fn packet(&mut self, timestamp: Instant, packet: &[u8]) {
         self.packet_header(timestamp, packet.len());
-        self.write(packet);
+        let captured_len = core::cmp::min(packet.len(), self.snaplen() as usize);
+        self.write(&packet[..captured_len]);
         self.flush();
     }
 }
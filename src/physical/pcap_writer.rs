@@ -0,0 +1,118 @@
+//! Tees frames from a wrapped [`Device`] into a [`PcapSink`] for offline inspection.
+
+use core::cell::RefCell;
+
+use crate::physical::packet_capture::{PcapLink, PcapSink};
+use crate::physical::{Device, DeviceCapabilities, Medium, RxToken, TxToken};
+use crate::time::Instant;
+
+pub use crate::physical::packet_capture::PcapMode;
+
+/// A [`Device`] wrapper that writes the frames it sees in the directions selected
+/// by its [`PcapMode`] into a [`PcapSink`], so a
+/// logging layer can be dropped between a stack and its hardware and the result
+/// opened directly in Wireshark.
+pub struct PcapWriter<D: Device, S: PcapSink> {
+    inner: D,
+    sink: RefCell<S>,
+    mode: PcapMode,
+}
+
+impl<D: Device, S: PcapSink> PcapWriter<D, S> {
+    /// Wraps `inner`, writing frames allowed by `mode` into `sink` as they pass
+    /// through. Writes the pcap global header immediately, tagging captures
+    /// with the link type derived from `inner`'s medium.
+    pub fn new(inner: D, sink: S, mode: PcapMode) -> Self {
+        let link = match inner.capabilities().medium {
+            Medium::Ethernet => PcapLink::Ethernet,
+            Medium::Ip => PcapLink::Ip,
+        };
+        let sink = RefCell::new(sink);
+        sink.borrow_mut().global_header(link);
+        PcapWriter { inner, sink, mode }
+    }
+
+    fn rx_sink(sink: &RefCell<S>, mode: PcapMode) -> Option<&RefCell<S>> {
+        matches!(mode, PcapMode::Both | PcapMode::Rx).then_some(sink)
+    }
+
+    fn tx_sink(sink: &RefCell<S>, mode: PcapMode) -> Option<&RefCell<S>> {
+        matches!(mode, PcapMode::Both | PcapMode::Tx).then_some(sink)
+    }
+}
+
+/// An [`RxToken`] that mirrors the frame it wraps into a [`PcapSink`].
+pub struct PcapWriterRxToken<'a, T, S> {
+    token: T,
+    sink: Option<&'a RefCell<S>>,
+}
+
+impl<'a, T: RxToken, S: PcapSink> RxToken for PcapWriterRxToken<'a, T, S> {
+    fn consume<R, F: FnOnce(&mut [u8]) -> R>(self, timestamp: Instant, f: F) -> R {
+        let sink = self.sink;
+        self.token.consume(timestamp, |buffer| {
+            if let Some(sink) = sink {
+                sink.borrow_mut().packet(timestamp, buffer);
+            }
+            f(buffer)
+        })
+    }
+}
+
+/// A [`TxToken`] that mirrors the frame it wraps into a [`PcapSink`].
+pub struct PcapWriterTxToken<'a, T, S> {
+    token: T,
+    sink: Option<&'a RefCell<S>>,
+}
+
+impl<'a, T: TxToken, S: PcapSink> TxToken for PcapWriterTxToken<'a, T, S> {
+    fn consume<R, F: FnOnce(&mut [u8]) -> R>(self, timestamp: Instant, len: usize, f: F) -> R {
+        let sink = self.sink;
+        self.token.consume(timestamp, len, |buffer| {
+            // `f` fills the transmit buffer, so the frame can only be captured
+            // once it runs, not before.
+            let result = f(buffer);
+            if let Some(sink) = sink {
+                sink.borrow_mut().packet(timestamp, buffer);
+            }
+            result
+        })
+    }
+}
+
+impl<D: Device, S: PcapSink> Device for PcapWriter<D, S> {
+    type RxToken<'t>
+        = PcapWriterRxToken<'t, D::RxToken<'t>, S>
+    where
+        Self: 't;
+    type TxToken<'t>
+        = PcapWriterTxToken<'t, D::TxToken<'t>, S>
+    where
+        Self: 't;
+
+    fn receive(&mut self, timestamp: Instant) -> Option<(Self::RxToken<'_>, Self::TxToken<'_>)> {
+        let (rx, tx) = self.inner.receive(timestamp)?;
+        Some((
+            PcapWriterRxToken {
+                token: rx,
+                sink: Self::rx_sink(&self.sink, self.mode),
+            },
+            PcapWriterTxToken {
+                token: tx,
+                sink: Self::tx_sink(&self.sink, self.mode),
+            },
+        ))
+    }
+
+    fn transmit(&mut self, timestamp: Instant) -> Option<Self::TxToken<'_>> {
+        let token = self.inner.transmit(timestamp)?;
+        Some(PcapWriterTxToken {
+            token,
+            sink: Self::tx_sink(&self.sink, self.mode),
+        })
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        self.inner.capabilities()
+    }
+}
@@ -3,7 +3,7 @@
 use core::{fmt, ops};
 
 /// Represents an absolute time value.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct Instant {
     micros: i64,
 }
@@ -32,4 +32,124 @@ impl Instant {
     const fn from_micros_const(micros: i64) -> Self {
         Self { micros }
     }
+
+    /// Returns the number of whole seconds in this `Instant`.
+    pub const fn secs(&self) -> i64 {
+        self.micros / 1_000_000
+    }
+
+    /// Returns the fractional number of microseconds in this `Instant`.
+    pub const fn micros(&self) -> i64 {
+        self.micros % 1_000_000
+    }
+
+    /// Returns the total number of microseconds in this `Instant`.
+    pub const fn total_micros(&self) -> i64 {
+        self.micros
+    }
+}
+
+/// A relative amount of time.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Duration {
+    micros: u64,
+}
+
+impl Duration {
+    pub const ZERO: Duration = Duration::from_micros(0);
+
+    pub const fn from_micros(micros: u64) -> Self {
+        Self { micros }
+    }
+
+    pub const fn from_millis(millis: u64) -> Self {
+        Self {
+            micros: millis * 1000,
+        }
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Self {
+            micros: secs * 1_000_000,
+        }
+    }
+
+    /// Returns the total number of microseconds in this `Duration`.
+    pub const fn total_micros(&self) -> u64 {
+        self.micros
+    }
+
+    /// Returns the total number of milliseconds in this `Duration`.
+    pub const fn total_millis(&self) -> u64 {
+        self.micros / 1000
+    }
+}
+
+impl ops::Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        Instant::from_micros(self.micros + rhs.micros as i64)
+    }
+}
+
+impl ops::AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, rhs: Duration) {
+        self.micros += rhs.micros as i64;
+    }
+}
+
+impl ops::Sub<Duration> for Instant {
+    type Output = Instant;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Instant::from_micros(self.micros - rhs.micros as i64)
+    }
+}
+
+impl ops::SubAssign<Duration> for Instant {
+    fn sub_assign(&mut self, rhs: Duration) {
+        self.micros -= rhs.micros as i64;
+    }
+}
+
+impl ops::Sub<Instant> for Instant {
+    type Output = Duration;
+
+    fn sub(self, rhs: Instant) -> Self::Output {
+        Duration::from_micros((self.micros - rhs.micros).unsigned_abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instant_add_duration() {
+        let t = Instant::from_secs(1) + Duration::from_millis(500);
+        assert_eq!(t.total_micros(), 1_500_000);
+    }
+
+    #[test]
+    fn instant_sub_duration() {
+        let t = Instant::from_secs(2) - Duration::from_millis(500);
+        assert_eq!(t.total_micros(), 1_500_000);
+    }
+
+    #[test]
+    fn instant_sub_instant_yields_duration() {
+        let a = Instant::from_secs(3);
+        let b = Instant::from_secs(1);
+        assert_eq!(a - b, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn add_assign_and_sub_assign() {
+        let mut t = Instant::from_secs(1);
+        t += Duration::from_millis(250);
+        assert_eq!(t.total_micros(), 1_250_000);
+        t -= Duration::from_millis(250);
+        assert_eq!(t.total_micros(), 1_000_000);
+    }
 }